@@ -0,0 +1,80 @@
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::Signal;
+
+/// Detrending strategy applied to a digit sequence before computing its
+/// power spectrum, since the DC component (or, for a ramp-like signal, the
+/// linear component) otherwise swamps the rest of the spectrum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detrend {
+    /// Leave the digit sequence untouched.
+    None,
+    /// Subtract the mean from every sample.
+    Mid,
+    /// Fit a least-squares line to the sequence and subtract it.
+    Linear,
+}
+
+impl Detrend {
+    fn apply(self, digits: &[i32]) -> Vec<f64> {
+        let samples: Vec<f64> = digits.iter().map(|&d| f64::from(d)).collect();
+        match self {
+            Detrend::None => samples,
+            Detrend::Mid => {
+                let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+                samples.into_iter().map(|y| y - mean).collect()
+            }
+            Detrend::Linear => {
+                let n = samples.len() as f64;
+                let x_mean = (n - 1.0) / 2.0;
+                let y_mean = samples.iter().sum::<f64>() / n;
+                let (mut num, mut den) = (0.0, 0.0);
+                for (i, &y) in samples.iter().enumerate() {
+                    let x = i as f64 - x_mean;
+                    num += x * (y - y_mean);
+                    den += x * x;
+                }
+                let slope = if den == 0.0 { 0.0 } else { num / den };
+                let intercept = y_mean - slope * x_mean;
+                samples
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, y)| y - (slope * i as f64 + intercept))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Detrend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Detrend::None),
+            "mid" => Ok(Detrend::Mid),
+            "linear" => Ok(Detrend::Linear),
+            other => Err(format!(
+                "unknown detrend mode {:?} (expected \"none\", \"mid\", or \"linear\")",
+                other
+            )),
+        }
+    }
+}
+
+impl Signal {
+    /// Computes the power spectrum (magnitude squared of each FFT bin) of
+    /// this signal's digits, after applying `detrend`, so the frequency
+    /// content of the (flawed) transform can be inspected across phases.
+    pub fn power_spectrum(&self, detrend: Detrend) -> Vec<f64> {
+        let samples = detrend.apply(&self.digits);
+        let mut buffer: Vec<Complex<f64>> = samples.into_iter().map(|v| Complex::new(v, 0.0)).collect();
+
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(buffer.len());
+        fft.process(&mut buffer);
+
+        buffer.iter().map(Complex::norm_sqr).collect()
+    }
+}
@@ -1,15 +1,41 @@
 use rayon::prelude::*;
 use std::cmp::min;
+use std::fmt;
 use std::ops::Range;
 
+mod analysis;
+mod parse;
+
+pub use analysis::Detrend;
+pub use parse::{parse_signal, ParseSignalError};
+
+/// The base pattern used by the original AoC 2019 day 16 puzzle, before it
+/// is stretched per digit position: `[0, 1, 0, -1]`.
+pub const DEFAULT_BASE_PATTERN: [i32; 4] = [0, 1, 0, -1];
+
+/// The modulus used by the original puzzle: each derived digit is reduced
+/// mod 10.
+pub const DEFAULT_MODULUS: i32 = 10;
+
 #[derive(Debug)]
 pub struct Signal {
     pub digits: Vec<i32>,
     prefix_sum: Vec<i32>,
+    base_pattern: Vec<i32>,
+    modulus: i32,
 }
 
 impl Signal {
+    /// Builds a signal using the puzzle's default base pattern `[0, 1, 0,
+    /// -1]` and modulus `10`.
     pub fn new(digits: Vec<i32>) -> Self {
+        Self::with_pattern(digits, DEFAULT_BASE_PATTERN.to_vec(), DEFAULT_MODULUS)
+    }
+
+    /// Builds a signal using a custom base pattern and modulus, so that
+    /// variant frequency transforms (different kernels, different radices)
+    /// can be modeled rather than only the single AoC 2019 instance.
+    pub fn with_pattern(digits: Vec<i32>, base_pattern: Vec<i32>, modulus: i32) -> Self {
         let prefix_sum: Vec<i32> = std::iter::once(&0)
             .chain(digits.iter())
             .scan(0, |state, &x| {
@@ -17,7 +43,12 @@ impl Signal {
                 Some(*state)
             })
             .collect();
-        Signal { digits, prefix_sum }
+        Signal {
+            digits,
+            prefix_sum,
+            base_pattern,
+            modulus,
+        }
     }
 
     fn range_sum(&self, range: Range<usize>) -> i32 {
@@ -27,24 +58,151 @@ impl Signal {
         self.prefix_sum[range.start] - self.prefix_sum[range.end]
     }
 
+    /// Computes the digit at `repeat_size` (the 1-based output position) by
+    /// walking the base pattern stretched `repeat_size` times per entry and
+    /// cyclically repeated, with its leading element dropped (the AoC
+    /// encoding indexes the pattern starting one past the output position
+    /// itself). Each stretched-pattern entry is a contiguous run of equal
+    /// coefficients, so every run with a non-zero coefficient is folded in
+    /// via a single prefix-sum range query rather than a per-digit sum.
     pub fn compute_digit(&self, repeat_size: usize) -> i32 {
-        let offsets_and_signs = (repeat_size - 1..self.digits.len())
-            .step_by(2 * repeat_size)
-            .zip([1, -1].iter().cloned().cycle());
-        let accm = offsets_and_signs.fold(0, |accm, (offset, sign)| {
-            let lo = min(offset, self.digits.len());
-            let hi = min(offset + repeat_size, self.digits.len());
-            accm + (sign) * self.range_sum(lo..hi)
-        });
-        accm.abs() % 10
+        let n = self.digits.len();
+        let pattern_len = self.base_pattern.len();
+        let mut accm: i64 = 0;
+        let mut block_start = 0;
+        let mut block_index = 0;
+        while block_start < n {
+            let value = self.base_pattern[block_index % pattern_len];
+            let block_end = min((block_index + 1) * repeat_size - 1, n);
+            if value != 0 {
+                accm += i64::from(value) * i64::from(self.range_sum(block_start..block_end));
+            }
+            block_start = block_end;
+            block_index += 1;
+        }
+        (accm.abs() % i64::from(self.modulus)) as i32
     }
 
     pub fn fft_derive(&self) -> Self {
-        Self::new(
-            (1..=self.digits.len())
-                .into_par_iter()
-                .map(|x| self.compute_digit(x))
-                .collect(),
-        )
+        let digits = (1..=self.digits.len())
+            .into_par_iter()
+            .map(|x| self.compute_digit(x))
+            .collect();
+        Self::with_pattern(digits, self.base_pattern.clone(), self.modulus)
+    }
+
+    /// Derives the next phase for positions `from..` only, exploiting the
+    /// fact that once `from >= digits.len() / 2`, every pattern coefficient
+    /// for a position `i >= from` is exactly `1` under the default base
+    /// pattern (the zero and negative segments fall past the end of the
+    /// signal). The phase then collapses into a reverse running (suffix)
+    /// sum, reduced like `compute_digit` (absolute value mod
+    /// `self.modulus`). The summation covers only the `n - from` tail
+    /// positions instead of the general `compute_digit` path; building the
+    /// returned signal still copies all `n` digits and rebuilds its prefix
+    /// sums, so each call is `O(n)` overall.
+    ///
+    /// This "coefficient is always 1" property only holds for
+    /// `DEFAULT_BASE_PATTERN`; a custom pattern supplied via
+    /// `with_pattern` can have a nonzero coefficient anywhere, so this is a
+    /// hard (not debug-only) assertion rather than a silently wrong answer.
+    ///
+    /// Positions below `from` are left as-is in the returned signal and
+    /// should not be relied upon by callers.
+    pub fn fft_derive_tail(&self, from: usize) -> Self {
+        assert!(
+            self.base_pattern == DEFAULT_BASE_PATTERN,
+            "fft_derive_tail only supports the default base pattern {:?}, got {:?}",
+            DEFAULT_BASE_PATTERN,
+            self.base_pattern,
+        );
+        assert!(
+            from >= self.digits.len() / 2,
+            "fft_derive_tail requires from ({}) to be at or past the midpoint of a signal of length {}",
+            from,
+            self.digits.len(),
+        );
+        let mut new_digits = self.digits.clone();
+        let modulus = i64::from(self.modulus);
+        let mut running: i64 = 0;
+        for i in (from..self.digits.len()).rev() {
+            running += i64::from(self.digits[i]);
+            new_digits[i] = (running.abs() % modulus) as i32;
+        }
+        Self::with_pattern(new_digits, self.base_pattern.clone(), self.modulus)
+    }
+}
+
+/// Error returned when `transform`'s parameters don't make sense for the
+/// given signal, e.g. an offset past the end of the (repeated) signal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformError {
+    message: String,
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+/// Repeats `digits` `input_repeats` times, runs `fft_repeats` phases of the
+/// flawed frequency transform over the result, and returns the digits from
+/// `offset` onward.
+///
+/// This is the single entry point both the "part 1" style (no repeat, small
+/// offset) and "part 2" style (large repeat, large offset) invocations funnel
+/// through, so callers only need to pick the right parameters rather than
+/// editing source.
+pub fn transform(
+    digits: &[i32],
+    input_repeats: usize,
+    fft_repeats: usize,
+    offset: usize,
+) -> Result<Vec<i32>, TransformError> {
+    transform_with_callback(digits, input_repeats, fft_repeats, offset, |_, _| {})
+}
+
+/// Same as [`transform`], but additionally invokes `on_phase(phase, signal)`
+/// after every completed phase (`phase` is 1-based), so callers can inspect
+/// intermediate state — e.g. dumping a power spectrum — without duplicating
+/// the phase loop.
+pub fn transform_with_callback(
+    digits: &[i32],
+    input_repeats: usize,
+    fft_repeats: usize,
+    offset: usize,
+    mut on_phase: impl FnMut(usize, &Signal),
+) -> Result<Vec<i32>, TransformError> {
+    let total_len = digits.len() * input_repeats;
+    if offset >= total_len {
+        return Err(TransformError {
+            message: format!(
+                "offset {} is out of range for a signal of length {} ({} digits repeated {} times)",
+                offset,
+                total_len,
+                digits.len(),
+                input_repeats,
+            ),
+        });
+    }
+
+    let ext_digits: Vec<i32> = digits
+        .iter()
+        .cloned()
+        .cycle()
+        .take(total_len)
+        .collect();
+    let mut signal = Signal::new(ext_digits);
+    for phase in 1..=fft_repeats {
+        signal = if offset >= total_len / 2 {
+            signal.fft_derive_tail(offset)
+        } else {
+            signal.fft_derive()
+        };
+        on_phase(phase, &signal);
     }
+    Ok(signal.digits[offset..].to_vec())
 }
@@ -1,53 +1,75 @@
-use std::env;
-use std::fs;
+use std::io::{self, BufRead};
 
-use indicatif::{ProgressBar, ProgressStyle};
+use clap::Parser;
 
-use day16_flawed_frequency_transmission::Signal;
+use day16_flawed_frequency_transmission::{parse_signal, transform, transform_with_callback, Detrend};
 
-fn main() {
-    let in_file = env::args().nth(1).expect("expected input file");
-    let contents = fs::read_to_string(in_file).expect("file exists");
-    let digits: Vec<i32> = contents
-        .chars()
-        .filter_map(|x| x.to_digit(10))
-        .map(|x| x as i32)
-        .collect();
-    part1_solve(digits.as_ref());
-    part2_solve(digits.as_ref());
-}
+/// Explore the flawed frequency transform (FFT) from AoC 2019 day 16 with a
+/// configurable offset, input-repeat factor, and phase count.
+///
+/// Signals are read line-by-line from stdin, so multiple signals can be
+/// explored in a single run.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Opts {
+    /// Zero-based digit offset into the (repeated) signal to read output from
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
 
-fn part1_solve(digits: &[i32]) {
-    let mut signal = Signal::new(digits.into());
-    for _ in 0..100 {
-        signal = signal.fft_derive();
-    }
-    let output = &signal.digits[..8];
-    println!("{:?}", output);
+    /// Number of times to repeat the input signal before transforming it
+    #[arg(long = "input-repeats", default_value_t = 1)]
+    input_repeats: usize,
+
+    /// Number of FFT phases to apply
+    #[arg(long = "fft-repeats", default_value_t = 100)]
+    fft_repeats: usize,
+
+    /// Number of digits to print starting at `offset`
+    #[arg(long = "output-digits", default_value_t = 8)]
+    output_digits: usize,
+
+    /// Dump the power spectrum of the signal after each phase, for studying
+    /// convergence of the transform
+    #[arg(long)]
+    dump_spectrum: bool,
+
+    /// Detrend mode applied before a spectrum dump: "none", "mid", or "linear"
+    #[arg(long, default_value = "none")]
+    detrend: Detrend,
 }
 
-fn part2_solve(digits: &[i32]) {
-    let offset = digits.iter().take(7).fold(0, |acc, x| acc * 10 + (*x)) as usize;
-    let ext_digits: Vec<i32> = std::iter::repeat(digits.iter())
-        .take(10_000)
-        .flatten()
-        .cloned()
-        .collect();
-    let mut signal = Signal::new(ext_digits);
-
-    let pb = ProgressBar::new(100);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{elapsed_precise}/-{eta_precise} {wide_bar} {pos:>3}/{len:3}"),
-    );
-
-    // let now = Instant::now();
-    for r in 1..=100 {
-        signal = signal.fft_derive();
-        pb.set_message(format!("round #{}", r).as_str());
-        pb.inc(1);
+fn main() {
+    let opts = Opts::parse();
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.expect("failed to read line from stdin");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let digits = parse_signal(&line).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        });
+
+        let output = if opts.dump_spectrum {
+            transform_with_callback(
+                &digits,
+                opts.input_repeats,
+                opts.fft_repeats,
+                opts.offset,
+                |phase, signal| {
+                    let spectrum = signal.power_spectrum(opts.detrend);
+                    println!("phase {}: spectrum = {:?}", phase, spectrum);
+                },
+            )
+        } else {
+            transform(&digits, opts.input_repeats, opts.fft_repeats, opts.offset)
+        };
+        let output = output.unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        });
+        let take = opts.output_digits.min(output.len());
+        println!("{:?}", &output[..take]);
     }
-    pb.finish();
-    let output = &signal.digits[offset..offset + 8];
-    println!("{:?}", output);
 }
@@ -0,0 +1,66 @@
+use std::fmt;
+
+/// Error produced when a signal string cannot be parsed into digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSignalError {
+    message: String,
+    position: usize,
+}
+
+impl fmt::Display for ParseSignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte offset {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseSignalError {}
+
+/// Parses a signal from text into a vector of digits.
+///
+/// Two encodings are accepted: a contiguous run of ASCII digits (the classic
+/// AoC encoding, e.g. `"80871224585914546619083218645595"`), or whitespace-
+/// or comma-separated tokens (e.g. `"1, 2, -3 10"`), which additionally
+/// allows values outside `0..=9` for experimentation. Unlike the old
+/// `filter_map(char::to_digit)` approach, any unrecognized character or
+/// token is reported as an error carrying its byte offset rather than
+/// silently dropped.
+pub fn parse_signal(input: &str) -> Result<Vec<i32>, ParseSignalError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(ParseSignalError {
+            message: "signal is empty".to_string(),
+            position: 0,
+        });
+    }
+
+    if trimmed.contains(|c: char| c.is_whitespace() || c == ',') {
+        let digits: Vec<i32> = trimmed
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token.parse::<i32>().map_err(|_| ParseSignalError {
+                    message: format!("invalid token {:?}", token),
+                    position: token.as_ptr() as usize - input.as_ptr() as usize,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        if digits.is_empty() {
+            return Err(ParseSignalError {
+                message: "signal is empty".to_string(),
+                position: 0,
+            });
+        }
+        Ok(digits)
+    } else {
+        let trim_offset = trimmed.as_ptr() as usize - input.as_ptr() as usize;
+        trimmed
+            .char_indices()
+            .map(|(position, c)| {
+                c.to_digit(10).map(|d| d as i32).ok_or_else(|| ParseSignalError {
+                    message: format!("invalid digit character {:?}", c),
+                    position: trim_offset + position,
+                })
+            })
+            .collect()
+    }
+}